@@ -0,0 +1,194 @@
+use crate::{GraphGuard, NodeGuard, NodePtr, Successors};
+use std::collections::HashMap;
+
+/// Immediate-dominator information computed by [`GraphGuard::dominators`].
+pub struct Dominators<N> {
+    root: NodePtr<N>,
+    idom: HashMap<NodePtr<N>, NodePtr<N>>,
+}
+
+impl<N> Dominators<N> {
+    /// Whether `node` was reachable from the root the dominators were
+    /// computed from.
+    pub fn is_reachable(&self, node: NodePtr<N>) -> bool {
+        self.idom.contains_key(&node)
+    }
+
+    /// The immediate dominator of `node`, or `None` for the root itself (or
+    /// for a node that was never reached).
+    pub fn immediate_dominator(&self, node: NodePtr<N>) -> Option<NodePtr<N>> {
+        if node == self.root {
+            return None;
+        }
+        self.idom.get(&node).copied()
+    }
+
+    /// Walks from `node` up through its dominators, yielding `node` itself
+    /// first and ending at (and including) the root.
+    pub fn dominators(&self, node: NodePtr<N>) -> DominatorChain<'_, N> {
+        DominatorChain {
+            dominators: self,
+            current: self.is_reachable(node).then_some(node),
+        }
+    }
+}
+
+/// Iterator returned by [`Dominators::dominators`].
+pub struct DominatorChain<'a, N> {
+    dominators: &'a Dominators<N>,
+    current: Option<NodePtr<N>>,
+}
+
+impl<N> Iterator for DominatorChain<'_, N> {
+    type Item = NodePtr<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current?;
+        self.current = self.dominators.immediate_dominator(node);
+        Some(node)
+    }
+}
+
+impl<'gg, N: Successors> GraphGuard<'gg, N> {
+    /// Computes the immediate dominator of every node reachable from `root`,
+    /// using the iterative Cooper-Harvey-Kennedy algorithm.
+    pub fn dominators(&self, root: NodeGuard<'gg, N>) -> Dominators<N> {
+        let root_ptr = unsafe { root.make_ptr() };
+
+        // `post_order` finishes `root` last, so reversing it yields a
+        // reverse-postorder where the root comes first and every node
+        // appears after all of its predecessors except those reaching it
+        // through a back edge (which the sweep below skips until they have
+        // an idom). `intersect` walks the postorder numbering.
+        let postorder: Vec<NodeGuard<'gg, N>> = self.post_order([root]).collect();
+        let po_number: HashMap<NodePtr<N>, usize> = postorder
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (unsafe { node.make_ptr() }, i))
+            .collect();
+        let rpo: Vec<NodePtr<N>> = postorder
+            .iter()
+            .rev()
+            .map(|node| unsafe { node.make_ptr() })
+            .collect();
+
+        let mut predecessors: HashMap<NodePtr<N>, Vec<NodePtr<N>>> = HashMap::new();
+        for node in &postorder {
+            let node_ptr = unsafe { node.make_ptr() };
+            for succ in self.successors_of(*node) {
+                predecessors
+                    .entry(unsafe { succ.make_ptr() })
+                    .or_default()
+                    .push(node_ptr);
+            }
+        }
+
+        let mut idom: HashMap<NodePtr<N>, NodePtr<N>> = HashMap::new();
+        idom.insert(root_ptr, root_ptr);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &node in rpo.iter().filter(|&&node| node != root_ptr) {
+                let Some(preds) = predecessors.get(&node) else {
+                    continue;
+                };
+
+                let mut new_idom = None;
+                for &pred in preds {
+                    if !idom.contains_key(&pred) {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => pred,
+                        Some(current) => intersect(current, pred, &idom, &po_number),
+                    });
+                }
+
+                if let Some(new_idom) = new_idom {
+                    if idom.get(&node) != Some(&new_idom) {
+                        idom.insert(node, new_idom);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        Dominators {
+            root: root_ptr,
+            idom,
+        }
+    }
+}
+
+/// Walks two idom-tree fingers toward the root, using postorder numbers to
+/// decide which finger is further from the root, until they meet.
+fn intersect<N>(
+    mut a: NodePtr<N>,
+    mut b: NodePtr<N>,
+    idom: &HashMap<NodePtr<N>, NodePtr<N>>,
+    po_number: &HashMap<NodePtr<N>, usize>,
+) -> NodePtr<N> {
+    while a != b {
+        while po_number[&a] < po_number[&b] {
+            a = idom[&a];
+        }
+        while po_number[&b] < po_number[&a] {
+            b = idom[&b];
+        }
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Graph;
+    use std::cell::RefCell;
+
+    struct Node {
+        edges: RefCell<Vec<NodePtr<Node>>>,
+    }
+
+    impl Successors for Node {
+        type Iter = std::vec::IntoIter<NodePtr<Node>>;
+        fn successors(&self) -> Self::Iter {
+            self.edges.borrow().clone().into_iter()
+        }
+    }
+
+    #[test]
+    fn diamond_dominates_through_the_join() {
+        // root -> (b, c) -> d
+        let graph = Graph::<Node>::new();
+        graph.with(|g| {
+            let root = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            let b = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            let c = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            let d = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            root.edges.borrow_mut().push(unsafe { b.make_ptr() });
+            root.edges.borrow_mut().push(unsafe { c.make_ptr() });
+            b.edges.borrow_mut().push(unsafe { d.make_ptr() });
+            c.edges.borrow_mut().push(unsafe { d.make_ptr() });
+
+            let dom = g.dominators(root);
+            let root_ptr = unsafe { root.make_ptr() };
+            assert_eq!(dom.immediate_dominator(unsafe { b.make_ptr() }), Some(root_ptr));
+            assert_eq!(dom.immediate_dominator(unsafe { c.make_ptr() }), Some(root_ptr));
+            // d is reached through both b and c, so its idom is root, not either branch.
+            assert_eq!(dom.immediate_dominator(unsafe { d.make_ptr() }), Some(root_ptr));
+            assert_eq!(dom.immediate_dominator(root_ptr), None);
+
+            let chain: Vec<_> = dom.dominators(unsafe { d.make_ptr() }).collect();
+            assert_eq!(chain, vec![unsafe { d.make_ptr() }, root_ptr]);
+        });
+    }
+}
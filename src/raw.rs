@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::ptr::NonNull;
@@ -5,6 +8,7 @@ use typed_arena::Arena;
 
 pub struct Graph<N> {
     graph: Arena<N>,
+    intern: RefCell<HashMap<u64, Vec<NodePtr<N>>>>,
 }
 
 pub struct GraphGuard<'gg, N> {
@@ -12,6 +16,14 @@ pub struct GraphGuard<'gg, N> {
     invariant: PhantomData<&'gg mut &'gg ()>,
 }
 
+impl<N> Clone for GraphGuard<'_, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N> Copy for GraphGuard<'_, N> {}
+
 impl<'gg, N> GraphGuard<'gg, N> {
     pub fn insert(&self, node: N) -> NodeGuard<'gg, N> {
         let node_ref = self.inside.graph.alloc(node);
@@ -29,6 +41,33 @@ impl<'gg, N> GraphGuard<'gg, N> {
     }
 }
 
+impl<'gg, N: Hash + Eq> GraphGuard<'gg, N> {
+    /// Inserts `node`, unless a structurally-equal node has already been
+    /// interned through this method, in which case the existing node is
+    /// returned instead of allocating a duplicate.
+    ///
+    /// Nodes are never moved out of the underlying arena, so a `NodePtr`
+    /// handed out this way stays valid for the lifetime of the graph.
+    pub fn insert_interned(&self, node: N) -> NodeGuard<'gg, N> {
+        let mut hasher = DefaultHasher::new();
+        node.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let mut buckets = self.inside.intern.borrow_mut();
+        let bucket = buckets.entry(hash).or_default();
+        for &ptr in bucket.iter() {
+            let existing = unsafe { self.lookup_ptr(ptr) };
+            if *existing == node {
+                return existing;
+            }
+        }
+
+        let inserted = self.insert(node);
+        bucket.push(unsafe { inserted.make_ptr() });
+        inserted
+    }
+}
+
 pub struct NodeGuard<'gg, N> {
     inside: &'gg N,
     invariant: PhantomData<&'gg mut &'gg ()>,
@@ -56,6 +95,7 @@ impl<N> Graph<N> {
     pub fn new() -> Self {
         Graph {
             graph: Arena::new(),
+            intern: RefCell::new(HashMap::new()),
         }
     }
 
@@ -156,4 +196,16 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {}
+
+    #[test]
+    fn insert_interned_dedupes_structurally_equal_nodes() {
+        let graph = Graph::<u32>::new();
+        graph.with(|g| {
+            let a = g.insert_interned(1);
+            let b = g.insert_interned(1);
+            let c = g.insert_interned(2);
+            assert!(a == b);
+            assert!(!(a == c));
+        });
+    }
 }
@@ -0,0 +1,171 @@
+use crate::{GraphGuard, NodeGuard, NodePtr};
+use std::collections::HashMap;
+
+/// Parent-to-child adjacency, with per-edge data, for tree-shaped graphs
+/// (such as the `parent`/`children`-cell trees the crate's examples build).
+///
+/// Implement this for a node type to use [`GraphGuard::reroot`].
+pub trait TreeEdges: Sized {
+    type Edge;
+    type Iter: Iterator<Item = (Self::Edge, NodePtr<Self>)>;
+
+    fn children(&self) -> Self::Iter;
+}
+
+/// The children of a single node, paired with the edge leading to each.
+type ChildList<N> = Vec<(<N as TreeEdges>::Edge, NodePtr<N>)>;
+
+impl<'gg, N: TreeEdges> GraphGuard<'gg, N> {
+    /// Computes, in O(N), the re-rooting dynamic-programming value for
+    /// *every* node in the tree reachable from `root` as though it were the
+    /// root, rather than the O(N^2) cost of re-running a rooted DP once per
+    /// node.
+    ///
+    /// `merge`/`identity` form the monoid values are combined with,
+    /// `apply_edge(value, edge)` carries a value across one tree edge, and
+    /// `finalize(aggregate, node)` turns the all-directions aggregate at a
+    /// node into the result returned for it. Leaves fold to `identity`, and
+    /// a single-node tree returns `finalize(&identity, root)`.
+    pub fn reroot<V, R>(
+        &self,
+        root: NodeGuard<'gg, N>,
+        merge: impl Fn(&V, &V) -> V,
+        identity: V,
+        apply_edge: impl Fn(&V, &N::Edge) -> V,
+        finalize: impl Fn(&V, &N) -> R,
+    ) -> HashMap<NodePtr<N>, R>
+    where
+        V: Clone,
+    {
+        let root_ptr = unsafe { root.make_ptr() };
+
+        // Pass 1 (post-order): `down[v]` folds the edge-applied
+        // contributions of v's children, ignoring everything outside v's
+        // subtree.
+        let mut children_of: HashMap<NodePtr<N>, ChildList<N>> = HashMap::new();
+        let mut down: HashMap<NodePtr<N>, V> = HashMap::new();
+        let mut post_order: Vec<NodeGuard<'gg, N>> = Vec::new();
+
+        children_of.insert(root_ptr, Vec::new());
+        let mut stack: Vec<(NodeGuard<'gg, N>, N::Iter)> = vec![(root, root.children())];
+
+        while let Some((node, children)) = stack.last_mut() {
+            let node = *node;
+            let node_ptr = unsafe { node.make_ptr() };
+
+            if let Some((edge, child_ptr)) = children.next() {
+                children_of.entry(node_ptr).or_default().push((edge, child_ptr));
+                children_of.insert(child_ptr, Vec::new());
+                let child = unsafe { self.lookup_ptr(child_ptr) };
+                stack.push((child, child.children()));
+                continue;
+            }
+
+            stack.pop();
+            let value = children_of[&node_ptr]
+                .iter()
+                .fold(identity.clone(), |acc, (edge, child_ptr)| {
+                    merge(&acc, &apply_edge(&down[child_ptr], edge))
+                });
+            down.insert(node_ptr, value);
+            post_order.push(node);
+        }
+
+        // Pass 2 (pre-order): `up[v]` is everything outside v's subtree, as
+        // seen through the edge leading into v. Each child of v receives
+        // the merge of its siblings' contributions (via prefix/suffix
+        // products, since `merge` need not be commutative) plus `up[v]`.
+        let mut up: HashMap<NodePtr<N>, V> = HashMap::new();
+        up.insert(root_ptr, identity.clone());
+
+        let mut results = HashMap::with_capacity(post_order.len());
+
+        for node in post_order.into_iter().rev() {
+            let node_ptr = unsafe { node.make_ptr() };
+            let children = &children_of[&node_ptr];
+
+            let edge_values: Vec<V> = children
+                .iter()
+                .map(|(edge, child_ptr)| apply_edge(&down[child_ptr], edge))
+                .collect();
+
+            let mut prefix = vec![identity.clone(); edge_values.len() + 1];
+            for (i, value) in edge_values.iter().enumerate() {
+                prefix[i + 1] = merge(&prefix[i], value);
+            }
+            let mut suffix = vec![identity.clone(); edge_values.len() + 1];
+            for (i, value) in edge_values.iter().enumerate().rev() {
+                suffix[i] = merge(value, &suffix[i + 1]);
+            }
+
+            let all_around = merge(&up[&node_ptr], &prefix[edge_values.len()]);
+            results.insert(node_ptr, finalize(&all_around, node.node()));
+
+            for (i, (edge, child_ptr)) in children.iter().enumerate() {
+                let excluding_child = merge(&prefix[i], &suffix[i + 1]);
+                let contribution = merge(&up[&node_ptr], &excluding_child);
+                up.insert(*child_ptr, apply_edge(&contribution, edge));
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Graph;
+    use std::cell::RefCell;
+
+    struct Node {
+        children: RefCell<Vec<NodePtr<Node>>>,
+    }
+
+    impl TreeEdges for Node {
+        type Edge = ();
+        type Iter = std::vec::IntoIter<((), NodePtr<Node>)>;
+        fn children(&self) -> Self::Iter {
+            self.children
+                .borrow()
+                .iter()
+                .map(|&child| ((), child))
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+    }
+
+    #[test]
+    fn every_node_sees_the_whole_tree() {
+        // A 3-node chain: root -> a -> b.
+        let graph = Graph::<Node>::new();
+        graph.with(|g| {
+            let root = g.insert(Node {
+                children: RefCell::new(Vec::new()),
+            });
+            let a = g.insert(Node {
+                children: RefCell::new(Vec::new()),
+            });
+            let b = g.insert(Node {
+                children: RefCell::new(Vec::new()),
+            });
+            root.children.borrow_mut().push(unsafe { a.make_ptr() });
+            a.children.borrow_mut().push(unsafe { b.make_ptr() });
+
+            // `value` is the size of a subtree excluding its own root, so
+            // crossing the edge into that root adds one for the root itself.
+            let counts = g.reroot(
+                root,
+                |a: &u64, b: &u64| a + b,
+                0u64,
+                |value: &u64, _edge: &()| value + 1,
+                |aggregate: &u64, _node: &Node| aggregate + 1,
+            );
+
+            assert_eq!(counts.len(), 3);
+            for count in counts.values() {
+                assert_eq!(*count, 3);
+            }
+        });
+    }
+}
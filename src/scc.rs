@@ -0,0 +1,224 @@
+use crate::{GraphGuard, NodeGuard, NodePtr, Successors};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// The strongly connected components of a graph, as computed by
+/// [`GraphGuard::strongly_connected_components`].
+pub struct Sccs<'gg, N> {
+    /// Components in reverse topological order of the condensation DAG: if
+    /// there is an edge from component `i` to component `j` then `i` comes
+    /// after `j` in this list.
+    pub components: Vec<Vec<NodeGuard<'gg, N>>>,
+    component_of: HashMap<NodePtr<N>, usize>,
+}
+
+impl<'gg, N> Sccs<'gg, N> {
+    /// The index into `components` of the component containing `ptr`.
+    ///
+    /// Panics if `ptr` was not reachable from the roots the SCCs were
+    /// computed from.
+    pub fn component_of(&self, ptr: NodePtr<N>) -> usize {
+        self.component_of[&ptr]
+    }
+}
+
+impl<'gg, N: Successors> Sccs<'gg, N> {
+    /// The DAG of components: `condensation()[i]` lists the indices of the
+    /// components that `i` has an edge into, deduplicated and sorted.
+    pub fn condensation(&self) -> Vec<Vec<usize>> {
+        self.components
+            .iter()
+            .enumerate()
+            .map(|(i, component)| {
+                let mut targets = BTreeSet::new();
+                for node in component {
+                    for succ in node.successors() {
+                        let j = self.component_of(succ);
+                        if j != i {
+                            targets.insert(j);
+                        }
+                    }
+                }
+                targets.into_iter().collect()
+            })
+            .collect()
+    }
+}
+
+impl<'gg, N: Successors> GraphGuard<'gg, N> {
+    /// Computes the strongly connected components reachable from `starts`
+    /// using Tarjan's algorithm, run iteratively so it can't blow the stack
+    /// on large graphs.
+    pub fn strongly_connected_components<I>(&self, starts: I) -> Sccs<'gg, N>
+    where
+        I: IntoIterator<Item = NodeGuard<'gg, N>>,
+    {
+        let mut tarjan = Tarjan {
+            guard: *self,
+            next_index: 0,
+            index: HashMap::new(),
+            lowlink: HashMap::new(),
+            on_stack: HashSet::new(),
+            stack: Vec::new(),
+            components: Vec::new(),
+        };
+
+        for start in starts {
+            if !tarjan.index.contains_key(&unsafe { start.make_ptr() }) {
+                tarjan.visit(start);
+            }
+        }
+
+        let mut component_of = HashMap::new();
+        for (i, component) in tarjan.components.iter().enumerate() {
+            for node in component {
+                component_of.insert(unsafe { node.make_ptr() }, i);
+            }
+        }
+
+        Sccs {
+            components: tarjan.components,
+            component_of,
+        }
+    }
+}
+
+/// Scratch state for one run of Tarjan's algorithm.
+struct Tarjan<'gg, N> {
+    guard: GraphGuard<'gg, N>,
+    next_index: usize,
+    index: HashMap<NodePtr<N>, usize>,
+    lowlink: HashMap<NodePtr<N>, usize>,
+    on_stack: HashSet<NodePtr<N>>,
+    stack: Vec<NodeGuard<'gg, N>>,
+    components: Vec<Vec<NodeGuard<'gg, N>>>,
+}
+
+impl<'gg, N: Successors> Tarjan<'gg, N> {
+    /// Runs the algorithm from `root`, which must not have been visited yet.
+    /// Iterative equivalent of the textbook recursive `strongconnect`: each
+    /// frame on `work` is a node together with the successors it still has
+    /// left to explore.
+    fn visit(&mut self, root: NodeGuard<'gg, N>) {
+        let mut work: Vec<(NodeGuard<'gg, N>, std::vec::IntoIter<NodeGuard<'gg, N>>)> = Vec::new();
+        self.open(root);
+        work.push((root, self.guard.successors_of(root).into_iter()));
+
+        while let Some((node, children)) = work.last_mut() {
+            let node = *node;
+            let node_ptr = unsafe { node.make_ptr() };
+
+            if let Some(child) = children.next() {
+                let child_ptr = unsafe { child.make_ptr() };
+                if !self.index.contains_key(&child_ptr) {
+                    self.open(child);
+                    work.push((child, self.guard.successors_of(child).into_iter()));
+                } else if self.on_stack.contains(&child_ptr) {
+                    let child_index = self.index[&child_ptr];
+                    let node_low = self.lowlink[&node_ptr];
+                    if child_index < node_low {
+                        self.lowlink.insert(node_ptr, child_index);
+                    }
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some((parent, _)) = work.last() {
+                let parent_ptr = unsafe { parent.make_ptr() };
+                let node_low = self.lowlink[&node_ptr];
+                let parent_low = self.lowlink[&parent_ptr];
+                if node_low < parent_low {
+                    self.lowlink.insert(parent_ptr, node_low);
+                }
+            }
+
+            if self.lowlink[&node_ptr] == self.index[&node_ptr] {
+                self.pop_component(node_ptr);
+            }
+        }
+    }
+
+    /// Assigns `node` its index/lowlink and pushes it onto the Tarjan stack.
+    fn open(&mut self, node: NodeGuard<'gg, N>) {
+        let ptr = unsafe { node.make_ptr() };
+        self.index.insert(ptr, self.next_index);
+        self.lowlink.insert(ptr, self.next_index);
+        self.next_index += 1;
+        self.on_stack.insert(ptr);
+        self.stack.push(node);
+    }
+
+    /// Pops the completed SCC rooted at `root_ptr` off the Tarjan stack.
+    fn pop_component(&mut self, root_ptr: NodePtr<N>) {
+        let mut component = Vec::new();
+        loop {
+            let w = self.stack.pop().expect("node should be on the Tarjan stack");
+            let w_ptr = unsafe { w.make_ptr() };
+            self.on_stack.remove(&w_ptr);
+            component.push(w);
+            if w_ptr == root_ptr {
+                break;
+            }
+        }
+        self.components.push(component);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Graph;
+    use std::cell::RefCell;
+
+    struct Node {
+        edges: RefCell<Vec<NodePtr<Node>>>,
+    }
+
+    impl Successors for Node {
+        type Iter = std::vec::IntoIter<NodePtr<Node>>;
+        fn successors(&self) -> Self::Iter {
+            self.edges.borrow().clone().into_iter()
+        }
+    }
+
+    #[test]
+    fn two_cycles_joined_by_a_bridge() {
+        // a <-> b, c <-> d, with a single bridge edge b -> c.
+        let graph = Graph::<Node>::new();
+        graph.with(|g| {
+            let a = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            let b = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            let c = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            let d = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            a.edges.borrow_mut().push(unsafe { b.make_ptr() });
+            b.edges.borrow_mut().push(unsafe { a.make_ptr() });
+            b.edges.borrow_mut().push(unsafe { c.make_ptr() });
+            c.edges.borrow_mut().push(unsafe { d.make_ptr() });
+            d.edges.borrow_mut().push(unsafe { c.make_ptr() });
+
+            let sccs = g.strongly_connected_components([a]);
+            assert_eq!(sccs.components.len(), 2);
+            assert_eq!(sccs.component_of(unsafe { a.make_ptr() }), sccs.component_of(unsafe { b.make_ptr() }));
+            assert_eq!(sccs.component_of(unsafe { c.make_ptr() }), sccs.component_of(unsafe { d.make_ptr() }));
+            assert_ne!(sccs.component_of(unsafe { a.make_ptr() }), sccs.component_of(unsafe { c.make_ptr() }));
+
+            // {a, b} comes after {c, d} in reverse topological order: the
+            // sink {c, d} is popped first and gets the lower index.
+            let ab = sccs.component_of(unsafe { a.make_ptr() });
+            let cd = sccs.component_of(unsafe { c.make_ptr() });
+            assert!(ab > cd);
+
+            let condensation = sccs.condensation();
+            assert_eq!(condensation[ab], vec![cd]);
+            assert!(condensation[cd].is_empty());
+        });
+    }
+}
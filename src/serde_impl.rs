@@ -0,0 +1,174 @@
+use crate::{GraphGuard, NodeGuard, NodePtr, Successors};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Lets a node type round-trip through `serde` despite storing raw
+/// `NodePtr` fields, which are only meaningful for the lifetime of one
+/// process and can't be serialized as-is.
+///
+/// Implement this to describe `Self`'s serializable shadow, `Ser`: how to
+/// build one while rewriting each outgoing pointer to a dense index, how to
+/// reconstruct a node's own data from a shadow, and how to patch a
+/// freshly-allocated node's interior pointer cells back in once every node
+/// in the graph exists.
+pub trait SerializePtr: Sized {
+    type Ser: Serialize + for<'de> Deserialize<'de>;
+
+    /// Builds the serializable shadow of `self`, translating each outgoing
+    /// `NodePtr` through `to_index`.
+    fn to_ser(&self, to_index: impl FnMut(NodePtr<Self>) -> u32) -> Self::Ser;
+
+    /// Reconstructs a node's own data from its shadow. Interior pointer
+    /// cells may be left however `Self` default-constructs them; `remap`
+    /// patches them in afterwards.
+    fn from_ser(ser: &Self::Ser) -> Self;
+
+    /// Patches `self`'s interior `NodePtr` cells from the indices recorded
+    /// in `ser`, translating each one through `lookup`.
+    fn remap(&self, ser: &Self::Ser, lookup: impl FnMut(u32) -> NodePtr<Self>);
+}
+
+/// The flat, index-based form of a graph produced by [`GraphGuard::serialize`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "N::Ser: Serialize",
+    deserialize = "N::Ser: for<'de2> Deserialize<'de2>"
+))]
+pub struct SerializedGraph<N: SerializePtr> {
+    nodes: Vec<N::Ser>,
+    roots: Vec<u32>,
+}
+
+impl<'gg, N: Successors + SerializePtr> GraphGuard<'gg, N> {
+    /// Walks every node reachable from `roots` and serializes it into a
+    /// flat node list, with every embedded `NodePtr<N>` rewritten to its
+    /// dense index in that list.
+    pub fn serialize<I>(&self, roots: I) -> SerializedGraph<N>
+    where
+        I: IntoIterator<Item = NodeGuard<'gg, N>>,
+    {
+        let roots: Vec<NodeGuard<'gg, N>> = roots.into_iter().collect();
+        let order: Vec<NodeGuard<'gg, N>> = self.breadth_first(roots.iter().copied()).collect();
+
+        let index_of: HashMap<NodePtr<N>, u32> = order
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (unsafe { node.make_ptr() }, i as u32))
+            .collect();
+
+        let nodes = order
+            .iter()
+            .map(|node| node.to_ser(|ptr| index_of[&ptr]))
+            .collect();
+
+        let root_indices = roots
+            .iter()
+            .map(|root| index_of[&unsafe { root.make_ptr() }])
+            .collect();
+
+        SerializedGraph {
+            nodes,
+            roots: root_indices,
+        }
+    }
+}
+
+impl<'gg, N: SerializePtr> GraphGuard<'gg, N> {
+    /// Allocates every node described by `data` into this graph, then
+    /// patches up their interior `NodePtr` cells, returning the
+    /// deserialized roots in the same order they were passed to
+    /// [`GraphGuard::serialize`].
+    pub fn deserialize(&self, data: &SerializedGraph<N>) -> Vec<NodeGuard<'gg, N>> {
+        let nodes: Vec<NodeGuard<'gg, N>> = data
+            .nodes
+            .iter()
+            .map(|ser| self.insert(N::from_ser(ser)))
+            .collect();
+
+        let ptrs: Vec<NodePtr<N>> = nodes.iter().map(|node| unsafe { node.make_ptr() }).collect();
+
+        for (node, ser) in nodes.iter().zip(&data.nodes) {
+            node.remap(ser, |index| ptrs[index as usize]);
+        }
+
+        data.roots.iter().map(|&index| nodes[index as usize]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Graph;
+    use std::cell::RefCell;
+
+    struct Node {
+        value: u32,
+        edges: RefCell<Vec<NodePtr<Node>>>,
+    }
+
+    impl Successors for Node {
+        type Iter = std::vec::IntoIter<NodePtr<Node>>;
+        fn successors(&self) -> Self::Iter {
+            self.edges.borrow().clone().into_iter()
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct NodeSer {
+        value: u32,
+        edges: Vec<u32>,
+    }
+
+    impl SerializePtr for Node {
+        type Ser = NodeSer;
+
+        fn to_ser(&self, mut to_index: impl FnMut(NodePtr<Self>) -> u32) -> NodeSer {
+            NodeSer {
+                value: self.value,
+                edges: self.edges.borrow().iter().map(|&ptr| to_index(ptr)).collect(),
+            }
+        }
+
+        fn from_ser(ser: &NodeSer) -> Self {
+            Node {
+                value: ser.value,
+                edges: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn remap(&self, ser: &NodeSer, mut lookup: impl FnMut(u32) -> NodePtr<Self>) {
+            *self.edges.borrow_mut() = ser.edges.iter().map(|&index| lookup(index)).collect();
+        }
+    }
+
+    #[test]
+    fn round_trips_a_cycle_through_json() {
+        let graph = Graph::<Node>::new();
+        let json = graph.with(|g| {
+            let a = g.insert(Node {
+                value: 1,
+                edges: RefCell::new(Vec::new()),
+            });
+            let b = g.insert(Node {
+                value: 2,
+                edges: RefCell::new(Vec::new()),
+            });
+            a.edges.borrow_mut().push(unsafe { b.make_ptr() });
+            b.edges.borrow_mut().push(unsafe { a.make_ptr() }); // cycle back to a
+
+            let serialized = g.serialize([a]);
+            serde_json::to_string(&serialized).unwrap()
+        });
+
+        let other = Graph::<Node>::new();
+        other.with(|g| {
+            let serialized: SerializedGraph<Node> = serde_json::from_str(&json).unwrap();
+            let roots = g.deserialize(&serialized);
+            assert_eq!(roots.len(), 1);
+            assert_eq!(roots[0].value, 1);
+
+            let values: Vec<u32> = g.breadth_first(roots.iter().copied()).map(|n| n.value).collect();
+            assert_eq!(values, vec![1, 2]);
+        });
+    }
+}
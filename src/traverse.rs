@@ -0,0 +1,211 @@
+use crate::{GraphGuard, NodeGuard, NodePtr};
+use std::collections::{HashSet, VecDeque};
+
+/// Adjacency for a node type `N`: given a node, yields the `NodePtr`s it points at.
+///
+/// Implement this once for your node type and the traversal iterators on
+/// `GraphGuard` (`depth_first`, `breadth_first`, `post_order`) become available.
+///
+/// These iterators only have one mode, not the "tree edges only" vs. "all
+/// reachable" distinction a traversal can have in general: since they yield
+/// each reachable `NodeGuard` at most once, by construction they only ever
+/// descend along tree edges, and the set of nodes that produces is the same
+/// set reachable from `starts` either way. The distinction would only show
+/// up if back/cross edges were surfaced as edges in their own right, which
+/// would need an iterator over edges rather than nodes; nothing here does
+/// that yet.
+pub trait Successors: Sized {
+    type Iter: Iterator<Item = NodePtr<Self>>;
+
+    fn successors(&self) -> Self::Iter;
+}
+
+impl<'gg, N: Successors> GraphGuard<'gg, N> {
+    /// Pre-order depth-first traversal starting from `starts`, visiting each
+    /// reachable node exactly once (only tree edges of the search are
+    /// followed; edges back into an already-visited node are not).
+    pub fn depth_first<I>(&self, starts: I) -> DepthFirst<'gg, N>
+    where
+        I: IntoIterator<Item = NodeGuard<'gg, N>>,
+    {
+        let mut stack: Vec<_> = starts.into_iter().collect();
+        stack.reverse();
+        DepthFirst {
+            guard: *self,
+            visited: HashSet::new(),
+            stack,
+        }
+    }
+
+    /// Breadth-first traversal starting from `starts`, visiting each node
+    /// reachable from any of them exactly once.
+    pub fn breadth_first<I>(&self, starts: I) -> BreadthFirst<'gg, N>
+    where
+        I: IntoIterator<Item = NodeGuard<'gg, N>>,
+    {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        for start in starts {
+            if visited.insert(unsafe { start.make_ptr() }) {
+                queue.push_back(start);
+            }
+        }
+        BreadthFirst {
+            guard: *self,
+            visited,
+            queue,
+        }
+    }
+
+    /// Post-order traversal starting from `starts`: a node is yielded only
+    /// after all of the (unvisited) nodes reachable from it have been.
+    pub fn post_order<I>(&self, starts: I) -> PostOrder<'gg, N>
+    where
+        I: IntoIterator<Item = NodeGuard<'gg, N>>,
+    {
+        PostOrder {
+            guard: *self,
+            visited: HashSet::new(),
+            pending: starts.into_iter().collect(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl<'gg, N> GraphGuard<'gg, N> {
+    pub(crate) fn successors_of(&self, node: NodeGuard<'gg, N>) -> Vec<NodeGuard<'gg, N>>
+    where
+        N: Successors,
+    {
+        node.successors()
+            .map(|ptr| unsafe { self.lookup_ptr(ptr) })
+            .collect()
+    }
+}
+
+/// Iterator returned by [`GraphGuard::depth_first`].
+pub struct DepthFirst<'gg, N> {
+    guard: GraphGuard<'gg, N>,
+    visited: HashSet<NodePtr<N>>,
+    stack: Vec<NodeGuard<'gg, N>>,
+}
+
+impl<'gg, N: Successors> Iterator for DepthFirst<'gg, N> {
+    type Item = NodeGuard<'gg, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if !self.visited.insert(unsafe { node.make_ptr() }) {
+                continue;
+            }
+            let mut children = self.guard.successors_of(node);
+            children.reverse();
+            self.stack.extend(children);
+            return Some(node);
+        }
+        None
+    }
+}
+
+/// Iterator returned by [`GraphGuard::breadth_first`].
+pub struct BreadthFirst<'gg, N> {
+    guard: GraphGuard<'gg, N>,
+    visited: HashSet<NodePtr<N>>,
+    queue: VecDeque<NodeGuard<'gg, N>>,
+}
+
+impl<'gg, N: Successors> Iterator for BreadthFirst<'gg, N> {
+    type Item = NodeGuard<'gg, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        for child in self.guard.successors_of(node) {
+            if self.visited.insert(unsafe { child.make_ptr() }) {
+                self.queue.push_back(child);
+            }
+        }
+        Some(node)
+    }
+}
+
+/// Iterator returned by [`GraphGuard::post_order`].
+pub struct PostOrder<'gg, N> {
+    guard: GraphGuard<'gg, N>,
+    visited: HashSet<NodePtr<N>>,
+    pending: VecDeque<NodeGuard<'gg, N>>,
+    stack: Vec<(NodeGuard<'gg, N>, std::vec::IntoIter<NodeGuard<'gg, N>>)>,
+}
+
+impl<'gg, N: Successors> Iterator for PostOrder<'gg, N> {
+    type Item = NodeGuard<'gg, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((node, children)) = self.stack.last_mut() {
+                let node = *node;
+                if let Some(child) = children.next() {
+                    if self.visited.insert(unsafe { child.make_ptr() }) {
+                        let grandchildren = self.guard.successors_of(child);
+                        self.stack.push((child, grandchildren.into_iter()));
+                    }
+                    continue;
+                }
+                self.stack.pop();
+                return Some(node);
+            }
+
+            let start = self.pending.pop_front()?;
+            if self.visited.insert(unsafe { start.make_ptr() }) {
+                let children = self.guard.successors_of(start);
+                self.stack.push((start, children.into_iter()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Graph;
+    use std::cell::RefCell;
+
+    struct Node {
+        edges: RefCell<Vec<NodePtr<Node>>>,
+    }
+
+    impl Successors for Node {
+        type Iter = std::vec::IntoIter<NodePtr<Node>>;
+        fn successors(&self) -> Self::Iter {
+            self.edges.borrow().clone().into_iter()
+        }
+    }
+
+    #[test]
+    fn dfs_bfs_post_order_terminate_on_cycles() {
+        let graph = Graph::<Node>::new();
+        graph.with(|g| {
+            let a = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            let b = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            let c = g.insert(Node {
+                edges: RefCell::new(Vec::new()),
+            });
+            a.edges.borrow_mut().push(unsafe { b.make_ptr() });
+            b.edges.borrow_mut().push(unsafe { c.make_ptr() });
+            c.edges.borrow_mut().push(unsafe { a.make_ptr() }); // cycle back to a
+
+            let dfs: Vec<_> = g.depth_first([a]).map(|n| unsafe { n.make_ptr() }).collect();
+            assert_eq!(dfs.len(), 3);
+
+            let bfs: Vec<_> = g.breadth_first([a]).map(|n| unsafe { n.make_ptr() }).collect();
+            assert_eq!(bfs.len(), 3);
+
+            let post: Vec<_> = g.post_order([a]).map(|n| unsafe { n.make_ptr() }).collect();
+            assert_eq!(post.len(), 3);
+            assert!(post[2].ptr_eq(unsafe { a.make_ptr() }));
+        });
+    }
+}